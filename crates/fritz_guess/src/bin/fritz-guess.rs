@@ -82,14 +82,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let guesses = paths
         .into_par_iter()
         .map(|path: PathBuf| {
-            let mut file = BufReader::new(
-                File::open(&path).expect(&format!("Unable to read file '{:?}'.", &path.display())),
-            );
-
-            let guesser = MostFrequentLineByLine::try_new(&mut file).expect(&format!(
-                "Unable to read file contents for file: {:?}",
-                &path.display()
-            ));
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                // unreadable file (permissions/race): fail this path, not the whole batch.
+                Err(_) => return Guess::Fail(path.to_path_buf()),
+            };
+            let mut file = BufReader::new(file);
+
+            let guesser = match MostFrequentLineByLine::try_new_sampled(&mut file) {
+                Ok(guesser) => guesser,
+                // e.g. an unsupported encoding: degrade to a per-file failure rather than
+                // aborting the whole parallel run.
+                Err(_) => return Guess::Fail(path.to_path_buf()),
+            };
 
             if let Ok(guess) = guesser.guess(sep.iter()) {
                 let prev = counter.fetch_add(1, Ordering::SeqCst);
@@ -11,26 +11,204 @@ use thiserror::Error;
 #[macro_use]
 extern crate parameterized;
 
+/// Number of data rows sampled when deciding whether row 0 is a header.
+const HEADER_SAMPLE_ROWS: usize = 100;
+
+/// Fraction of cells that must agree for a column (or the header/data comparison) to count as
+/// decisive during header detection.
+const HEADER_TYPE_THRESHOLD: f64 = 0.8;
+
+/// A cheaply inferred cell type, used for header detection and column typing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellType {
+    Integer,
+    Float,
+    Text,
+}
+
+/// The outcome of header detection. `Undetermined` is reported when the test is inconclusive —
+/// most importantly for all-string tables, so they aren't falsely labelled as having a header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderGuess {
+    HasHeader,
+    NoHeader,
+    Undetermined,
+}
+
+/// Classify a single cell by attempting cheap integer then float parses, falling back to text.
+fn classify_cell(cell: &str) -> CellType {
+    let trimmed = cell.trim();
+
+    if trimmed.parse::<i64>().is_ok() {
+        CellType::Integer
+    } else if trimmed.parse::<f64>().is_ok() {
+        CellType::Float
+    } else {
+        CellType::Text
+    }
+}
+
+/// Default byte budget for [`MostFrequentLineByLine::try_new_sampled`] (64 KiB).
+pub const DEFAULT_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Default line budget for [`MostFrequentLineByLine::try_new_sampled`].
+pub const DEFAULT_SAMPLE_LINES: usize = 512;
+
 /// Decent effort guesser which implements guessing using a line-by-line winner takes all strategy.
 /// For a line, the winner is the separator which occurs most frequent.
 /// The overall winner is the separator which takes most wins.
 pub struct MostFrequentLineByLine {
     /// Contents of a file or
     content: String,
+    /// When set, candidate separators seen inside a quoted field are not counted.
+    quote: Option<char>,
 }
 
 impl MostFrequentLineByLine {
     pub fn try_new<R: Read>(source: &mut R) -> Result<MostFrequentLineByLine, Error> {
-        let mut buffer = String::new();
+        Ok(MostFrequentLineByLine {
+            content: read_source(source)?,
+            quote: None,
+        })
+    }
 
+    /// Like [`MostFrequentLineByLine::try_new`], but reads only a bounded sample of the source
+    /// using the default limits ([`DEFAULT_SAMPLE_BYTES`] / [`DEFAULT_SAMPLE_LINES`]).
+    /// The modal separator is almost always decidable from the first handful of lines, so this
+    /// keeps guessing bounded in memory even on multi-gigabyte files.
+    pub fn try_new_sampled<R: Read>(source: &mut R) -> Result<MostFrequentLineByLine, Error> {
+        Self::try_new_sampled_with(source, DEFAULT_SAMPLE_BYTES, DEFAULT_SAMPLE_LINES)
+    }
+
+    /// Read at most `max_bytes` bytes and `max_lines` lines from the source (`max_lines == 0` sets
+    /// no line limit), stopping at the last complete line boundary so a record cut off by the byte
+    /// budget doesn't skew the counts.
+    pub fn try_new_sampled_with<R: Read>(
+        source: &mut R,
+        max_bytes: usize,
+        max_lines: usize,
+    ) -> Result<MostFrequentLineByLine, Error> {
+        let mut bytes = Vec::new();
         source
-            .read_to_string(&mut buffer)
+            .take(max_bytes as u64)
+            .read_to_end(&mut bytes)
             .map_err(|err| Error::Io(err))?;
 
+        // Whether the byte budget was reached, i.e. whether the source may have been cut short.
+        let budget_hit = bytes.len() >= max_bytes;
+
+        // Sampling at the byte budget can land mid character; trim back to the last whole unit so
+        // a valid file never fails to decode merely because of where the budget fell.
+        if has_utf16_bom(&bytes) {
+            // UTF-16: round down to the last whole code unit.
+            if bytes.len() % 2 != 0 {
+                bytes.pop();
+            }
+            // Also drop a trailing high surrogate whose low half fell beyond the budget.
+            let len = bytes.len();
+            if budget_hit && len >= 4 {
+                let last = if bytes.starts_with(&[0xFE, 0xFF]) {
+                    u16::from_be_bytes([bytes[len - 2], bytes[len - 1]])
+                } else {
+                    u16::from_le_bytes([bytes[len - 2], bytes[len - 1]])
+                };
+                if (0xD800..=0xDBFF).contains(&last) {
+                    bytes.truncate(len - 2);
+                }
+            }
+        } else if let Err(err) = std::str::from_utf8(&bytes) {
+            // UTF-8: only trim a trailing sequence truncated by the budget (no `error_len`);
+            // genuinely invalid bytes are left in place so `decode_bytes` still reports
+            // `UnsupportedEncoding`.
+            if budget_hit && err.error_len().is_none() {
+                bytes.truncate(err.valid_up_to());
+            }
+        }
+
+        let mut content = decode_bytes(bytes)?;
+
+        // If we stopped at the byte budget we may have cut a line in half; drop the partial tail.
+        if budget_hit {
+            if let Some(boundary) = content.rfind('\n') {
+                content.truncate(boundary);
+            }
+        }
+
+        // Keep at most `max_lines` lines, cutting at the line boundary.
+        if max_lines > 0 {
+            if let Some((boundary, _)) = content.match_indices('\n').nth(max_lines - 1) {
+                content.truncate(boundary);
+            }
+        }
+
         Ok(MostFrequentLineByLine {
-            content: buffer.to_owned(),
+            content,
+            quote: None,
         })
     }
+
+    /// Opt into RFC-4180-style quoting using the default quote character (`"`).
+    /// Separators seen inside a quoted field are ignored while counting.
+    ///
+    /// Quotes are only tracked within a single line: a quoted field containing a raw `\n`
+    /// spans multiple logical lines here, so such a record is collapsed (the in-quotes state
+    /// resets at each line boundary). Folding over the whole content would be required to
+    /// honour quoted newlines.
+    pub fn quoted(self) -> Self {
+        self.with_quote('"')
+    }
+
+    /// Opt into quoting using a custom quote character. See [`MostFrequentLineByLine::quoted`]
+    /// for the handling of quoted newlines.
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    /// Count the candidate separators on a single line, honouring the configured quote state.
+    /// The counts are ordered by char, so a maximum taken over them resolves ties to the highest char.
+    fn count_line<'a>(
+        &self,
+        line: &str,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> BTreeMap<char, usize> {
+        count_separators(line, separators, self.quote)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Count the candidate separators on a single line. When `quote` is set, separators seen inside a
+/// quoted field are ignored; a doubled quote (`""`) inside a quoted field is treated as a literal
+/// and keeps the scan in-quotes.
+fn count_separators<'a>(
+    line: &str,
+    separators: impl Iterator<Item = &'a char> + Clone,
+    quote: Option<char>,
+) -> HashMap<char, usize> {
+    let mut counts = HashMap::<char, usize>::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if let Some(quote) = quote {
+            if char == quote {
+                if in_quotes && chars.peek() == Some(&quote) {
+                    // doubled quote: a literal quote, stay in-quotes
+                    chars.next();
+                } else {
+                    in_quotes = !in_quotes;
+                }
+                continue;
+            }
+        }
+
+        if !in_quotes && char.is_one_of(separators.clone()) {
+            *counts.entry(char).or_default() += 1;
+        }
+    }
+
+    counts
 }
 
 #[derive(Debug, Error)]
@@ -40,6 +218,60 @@ pub enum Error {
 
     #[error("The file doesn't contain any of the expected delimiters.")]
     NoDelimiterFound,
+
+    #[error("The file is not valid UTF-8 and its encoding is not supported.")]
+    UnsupportedEncoding,
+}
+
+/// Read a source into a `String`, sniffing a leading byte-order mark first.
+/// A UTF-8 BOM (`EF BB BF`) is stripped, and UTF-16 LE (`FF FE`) / BE (`FE FF`) inputs are
+/// transcoded into UTF-8. Bytes that are still not valid UTF-8 after BOM handling surface as
+/// [`Error::UnsupportedEncoding`] so callers can report a clear message instead of panicking.
+fn read_source<R: Read>(source: &mut R) -> Result<String, Error> {
+    let mut bytes = Vec::new();
+
+    source
+        .read_to_end(&mut bytes)
+        .map_err(|err| Error::Io(err))?;
+
+    decode_bytes(bytes)
+}
+
+/// Whether a byte slice begins with a UTF-16 LE or BE byte-order mark.
+fn has_utf16_bom(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0xFF, 0xFE]) || bytes.starts_with(&[0xFE, 0xFF])
+}
+
+/// Decode already-read bytes into a `String`, sniffing a leading byte-order mark first.
+fn decode_bytes(bytes: Vec<u8>) -> Result<String, Error> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8(bytes[3..].to_vec()).map_err(|_| Error::UnsupportedEncoding)
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        decode_utf16(&bytes[2..], false)
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        decode_utf16(&bytes[2..], true)
+    } else {
+        String::from_utf8(bytes).map_err(|_| Error::UnsupportedEncoding)
+    }
+}
+
+/// Transcode UTF-16 code units (after the BOM has been consumed) into a UTF-8 `String`.
+fn decode_utf16(bytes: &[u8], big_endian: bool) -> Result<String, Error> {
+    if bytes.len() % 2 != 0 {
+        return Err(Error::UnsupportedEncoding);
+    }
+
+    let units = bytes.chunks_exact(2).map(|pair| {
+        if big_endian {
+            u16::from_be_bytes([pair[0], pair[1]])
+        } else {
+            u16::from_le_bytes([pair[0], pair[1]])
+        }
+    });
+
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|_| Error::UnsupportedEncoding)
 }
 
 trait IsOneOf<'v, V: 'v> {
@@ -52,24 +284,100 @@ impl<'v, V: PartialEq + 'v> IsOneOf<'v, V> for V {
     }
 }
 
+/// The outcome of a guess, carrying the evidence that backs it.
+/// `separator` is the chosen delimiter, `confidence` is in `[0, 1]` and derived from the margin
+/// between the winning separator and the runner up, and `tallies` holds the per-candidate evidence
+/// (line-wins for the frequency strategy, which also decide the winner; lines matching the modal
+/// field count for the consistency strategy, whose winner is ranked by that count weighted by the
+/// mode).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dialect {
+    pub separator: char,
+    pub confidence: f64,
+    pub tallies: BTreeMap<char, usize>,
+}
+
 pub trait GuessSeparator {
     fn guess<'a>(&self, separators: impl Iterator<Item = &'a char> + Clone) -> Result<char, Error>;
+
+    /// Like [`GuessSeparator::guess`], but returns the full [`Dialect`] including a confidence
+    /// score and the per-candidate tallies. A weakly supported guess is reported as a
+    /// low-confidence `Dialect` rather than [`Error::NoDelimiterFound`]. [`MostFrequentLineByLine`]
+    /// only returns the error when none of the candidate separators were seen at all;
+    /// [`MostConsistentFieldCount`] returns it whenever no candidate has a modal field count above
+    /// one, even when a candidate occurs on some lines.
+    fn guess_dialect<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> Result<Dialect, Error>;
 }
 
-impl GuessSeparator for MostFrequentLineByLine {
-    /// Check which of the provided separators occurs most often per line.
-    /// That character which occurs most often 'wins' for a line.
-    /// The character which wins most lines will be accepted as guess.
-    fn guess<'a>(&self, separators: impl Iterator<Item = &'a char> + Clone) -> Result<char, Error> {
+/// Pick the winner from a tally, its confidence from the margin to the runner up, and bundle
+/// them into a [`Dialect`]. Every candidate separator is seeded at `0` so each one appears in the
+/// reported tallies, including those that won no line. The tally is iterated in ascending char order
+/// so ties resolve deterministically to the highest char, matching the rest of the crate.
+fn dialect_from_tallies<'a>(
+    mut tallies: BTreeMap<char, usize>,
+    separators: impl Iterator<Item = &'a char>,
+    total: usize,
+) -> Result<Dialect, Error> {
+    for separator in separators {
+        tallies.entry(*separator).or_insert(0);
+    }
+
+    let mut ranked: Vec<(char, usize)> = tallies.iter().map(|(c, n)| (*c, *n)).collect();
+    // stable sort by score ascending; the last element (highest char on ties) is the winner
+    ranked.sort_by(|lhs, rhs| lhs.1.cmp(&rhs.1));
+
+    let (separator, winner) = match ranked.last() {
+        // a winning score of 0 means no candidate was ever seen: nothing to guess
+        Some(&(separator, winner)) if winner > 0 => (separator, winner),
+        _ => return Err(Error::NoDelimiterFound),
+    };
+
+    let runner_up = ranked
+        .iter()
+        .rev()
+        .nth(1)
+        .map(|(_, score)| *score)
+        .unwrap_or(0);
+
+    let confidence = if total == 0 {
+        0.0
+    } else {
+        (winner.saturating_sub(runner_up) as f64 / total as f64).clamp(0.0, 1.0)
+    };
+
+    Ok(Dialect {
+        separator,
+        confidence,
+        tallies,
+    })
+}
+
+impl MostFrequentLineByLine {
+    /// Count, per candidate separator, how many lines it wins.
+    /// A line is won by the separator which occurs most often on it; ties within a line are
+    /// resolved by the deterministic char ordering of the per-line maximum.
+    fn line_wins<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> BTreeMap<char, usize> {
+        self.line_wins_after(0, separators)
+    }
+
+    /// As [`MostFrequentLineByLine::line_wins`], but skipping the first `skip` lines (used to
+    /// exclude a detected preamble from the vote).
+    fn line_wins_after<'a>(
+        &self,
+        skip: usize,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> BTreeMap<char, usize> {
         self.content
             .lines()
+            .skip(skip)
             .flat_map(|line| {
-                line.chars()
-                    .filter(|item| item.is_one_of(separators.clone()))
-                    .fold(HashMap::<char, usize>::new(), |mut acc, char| {
-                        *acc.entry(char).or_default() += 1;
-                        acc
-                    })
+                self.count_line(line, separators.clone())
                     .iter()
                     .max_by(|lhs, rhs| lhs.1.cmp(&rhs.1))
                     .map(|(char, _)| *char)
@@ -79,16 +387,345 @@ impl GuessSeparator for MostFrequentLineByLine {
                 *acc.entry(char).or_default() += 1;
                 acc
             })
+    }
+}
+
+impl GuessSeparator for MostFrequentLineByLine {
+    /// Check which of the provided separators occurs most often per line.
+    /// That character which occurs most often 'wins' for a line.
+    /// The character which wins most lines will be accepted as guess.
+    fn guess<'a>(&self, separators: impl Iterator<Item = &'a char> + Clone) -> Result<char, Error> {
+        self.line_wins(separators)
             .iter()
             .max_by(|lhs, rhs| lhs.1.cmp(&rhs.1))
             .ok_or_else(|| Error::NoDelimiterFound)
             .map(|(char, _)| *char)
     }
+
+    fn guess_dialect<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> Result<Dialect, Error> {
+        let total = self.content.lines().count();
+        dialect_from_tallies(self.line_wins(separators.clone()), separators, total)
+    }
+}
+
+impl MostFrequentLineByLine {
+    /// The per-line signature used for preamble detection: the separator which wins the line
+    /// paired with the field count it produces (occurrences + 1). Lines on which no candidate
+    /// separator occurs have no signature and can never anchor the table.
+    fn line_signature<'a>(
+        &self,
+        line: &str,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> Option<(char, usize)> {
+        self.count_line(line, separators)
+            .into_iter()
+            .max_by(|lhs, rhs| lhs.1.cmp(&rhs.1))
+            .map(|(sep, count)| (sep, count + 1))
+    }
+
+    /// Number of leading rows to treat as preamble (titles, timestamps, blank lines) before the
+    /// table begins. The table is anchored at the start of the longest run of consecutive lines
+    /// that share the same `(winning separator, field count)` signature; everything above that run
+    /// is preamble. Returns `0` when no such run exists.
+    pub fn preamble_len<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> usize {
+        let signatures: Vec<Option<(char, usize)>> = self
+            .content
+            .lines()
+            .map(|line| self.line_signature(line, separators.clone()))
+            .collect();
+
+        let mut best_start = 0;
+        let mut best_len = 0;
+        let mut run_start = 0;
+        let mut run_len = 0;
+
+        for (index, signature) in signatures.iter().enumerate() {
+            let continues = run_len > 0
+                && signature.is_some()
+                && *signature == signatures[run_start];
+
+            if continues {
+                run_len += 1;
+            } else {
+                run_start = index;
+                run_len = if signature.is_some() { 1 } else { 0 };
+            }
+
+            if run_len > best_len {
+                best_len = run_len;
+                best_start = run_start;
+            }
+        }
+
+        if best_len == 0 {
+            0
+        } else {
+            best_start
+        }
+    }
+
+    /// Guess the separator after discarding any leading preamble rows, returning the guess
+    /// together with the number of rows that were skipped. See [`MostFrequentLineByLine::preamble_len`]
+    /// for how the table is anchored.
+    pub fn guess_without_preamble<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> Result<(char, usize), Error> {
+        let skipped = self.preamble_len(separators.clone());
+
+        let guess = self
+            .line_wins_after(skipped, separators)
+            .iter()
+            .max_by(|lhs, rhs| lhs.1.cmp(&rhs.1))
+            .ok_or_else(|| Error::NoDelimiterFound)
+            .map(|(char, _)| *char)?;
+
+        Ok((guess, skipped))
+    }
+
+    /// Decide whether row 0 is a header, given the chosen `separator`.
+    /// The first row and a sample of subsequent rows are split into fields and compared per column:
+    /// when the columns whose data rows parse as a concrete type (integer/float) have a text-valued
+    /// first row, the first row is reported as a header. Tables whose columns are all text give no
+    /// signal and return [`HeaderGuess::Undetermined`].
+    pub fn has_header(&self, separator: char) -> HeaderGuess {
+        let rows: Vec<Vec<&str>> = self
+            .content
+            .lines()
+            .map(|line| line.split(separator).collect())
+            .collect();
+
+        if rows.len() < 2 {
+            return HeaderGuess::Undetermined;
+        }
+
+        let header = &rows[0];
+        let sample: Vec<&Vec<&str>> = rows.iter().skip(1).take(HEADER_SAMPLE_ROWS).collect();
+
+        let mut concrete_columns = 0usize;
+        let mut text_header_over_concrete = 0usize;
+
+        for (col, cell) in header.iter().enumerate() {
+            let mut total = 0usize;
+            let mut concrete = 0usize;
+
+            for row in &sample {
+                if let Some(value) = row.get(col) {
+                    total += 1;
+                    if classify_cell(value) != CellType::Text {
+                        concrete += 1;
+                    }
+                }
+            }
+
+            if total == 0 {
+                continue;
+            }
+
+            if concrete as f64 / total as f64 >= HEADER_TYPE_THRESHOLD {
+                concrete_columns += 1;
+                if classify_cell(cell) == CellType::Text {
+                    text_header_over_concrete += 1;
+                }
+            }
+        }
+
+        if concrete_columns == 0 {
+            HeaderGuess::Undetermined
+        } else if text_header_over_concrete as f64 / concrete_columns as f64 >= HEADER_TYPE_THRESHOLD
+        {
+            HeaderGuess::HasHeader
+        } else {
+            HeaderGuess::NoHeader
+        }
+    }
+
+    /// Infer the dominant [`CellType`] of each column from a sample of the data rows (row 0 is
+    /// treated as a potential header and excluded). Handy as a starting point for schema inference.
+    pub fn column_types(&self, separator: char) -> Vec<CellType> {
+        let rows: Vec<Vec<&str>> = self
+            .content
+            .lines()
+            .skip(1)
+            .take(HEADER_SAMPLE_ROWS)
+            .map(|line| line.split(separator).collect())
+            .collect();
+
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        (0..columns)
+            .map(|col| {
+                let mut tally = [0usize; 3];
+                for row in &rows {
+                    if let Some(value) = row.get(col) {
+                        match classify_cell(value) {
+                            CellType::Integer => tally[0] += 1,
+                            CellType::Float => tally[1] += 1,
+                            CellType::Text => tally[2] += 1,
+                        }
+                    }
+                }
+
+                // Prefer the most common type, breaking ties towards the more general type
+                // (Text over Float over Integer) so a mixed column isn't over-committed.
+                if tally[2] >= tally[1] && tally[2] >= tally[0] {
+                    CellType::Text
+                } else if tally[1] >= tally[0] {
+                    CellType::Float
+                } else {
+                    CellType::Integer
+                }
+            })
+            .collect()
+    }
+}
+
+/// Guesser which prefers the separator that yields the most regular table shape,
+/// rather than the one which merely occurs most often.
+/// For each candidate separator every line is split into fields (the field count is the
+/// number of occurrences plus one); the modal field count across all lines is taken and the
+/// separator is scored by how many lines agree with that mode, weighted by the mode itself.
+/// Separators whose mode is `1` never actually split a line and are discarded.
+pub struct MostConsistentFieldCount {
+    content: String,
+}
+
+impl MostConsistentFieldCount {
+    pub fn try_new<R: Read>(source: &mut R) -> Result<MostConsistentFieldCount, Error> {
+        Ok(MostConsistentFieldCount {
+            content: read_source(source)?,
+        })
+    }
+
+    /// For each candidate separator, determine the modal field count and how many lines match it.
+    /// Separators whose mode is `1` (i.e. they never split a line) are discarded. The returned map
+    /// is keyed by separator and holds `(modal field count, lines matching the mode)`.
+    fn mode_matches<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> BTreeMap<char, (usize, usize)> {
+        separators
+            .clone()
+            // collect into a BTreeMap so the winner selection is deterministic for ties
+            .fold(BTreeMap::<char, (usize, usize)>::new(), |mut acc, separator| {
+                let field_counts = self.content.lines().fold(
+                    BTreeMap::<usize, usize>::new(),
+                    |mut hist, line| {
+                        let occurrences = count_separators(line, std::iter::once(separator), None)
+                            .get(separator)
+                            .copied()
+                            .unwrap_or(0);
+                        *hist.entry(occurrences + 1).or_default() += 1;
+                        hist
+                    },
+                );
+
+                if let Some((&mode, &matching)) =
+                    field_counts.iter().max_by(|lhs, rhs| lhs.1.cmp(&rhs.1))
+                {
+                    if mode > 1 {
+                        acc.insert(*separator, (mode, matching));
+                    }
+                }
+
+                acc
+            })
+    }
+}
+
+impl GuessSeparator for MostConsistentFieldCount {
+    /// Score each separator by the regularity of the resulting table shape.
+    /// A separator scores `(fraction of lines matching the modal field count) * (modal field count)`,
+    /// and separators whose mode is `1` (i.e. they never split a line) are discarded.
+    /// The highest scoring separator wins, ties broken by the deterministic char ordering.
+    fn guess<'a>(&self, separators: impl Iterator<Item = &'a char> + Clone) -> Result<char, Error> {
+        let lines = self.content.lines().count();
+
+        if lines == 0 {
+            return Err(Error::NoDelimiterFound);
+        }
+
+        self.mode_matches(separators)
+            .into_iter()
+            .map(|(sep, (mode, matching))| {
+                (sep, (matching as f64 / lines as f64) * mode as f64)
+            })
+            .max_by(|lhs, rhs| lhs.1.partial_cmp(&rhs.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(char, _)| char)
+            .ok_or_else(|| Error::NoDelimiterFound)
+    }
+
+    /// Report the winning separator along with the number of lines that matched each candidate's
+    /// modal field count as tallies. The winner is ranked by the same `(matching / lines) * mode`
+    /// score as [`GuessSeparator::guess`], so `separator` always equals what `guess` returns.
+    /// Confidence is the line-fraction margin between the winner and the runner up (by that same
+    /// ordering), so a separator that aligns far more lines than the next best is trusted more.
+    /// Returns [`Error::NoDelimiterFound`] when no candidate has a modal field count above one,
+    /// even if a candidate occurs on some lines.
+    fn guess_dialect<'a>(
+        &self,
+        separators: impl Iterator<Item = &'a char> + Clone,
+    ) -> Result<Dialect, Error> {
+        let total = self.content.lines().count();
+        let mode_matches = self.mode_matches(separators.clone());
+
+        // Rank by the same score as `guess`. The map iterates in ascending char order and the
+        // sort is stable, so ties resolve to the highest char — matching `guess`'s `max_by`.
+        let mut ranked: Vec<(char, usize, f64)> = mode_matches
+            .iter()
+            .map(|(&sep, &(mode, matching))| {
+                let score = if total == 0 {
+                    0.0
+                } else {
+                    (matching as f64 / total as f64) * mode as f64
+                };
+                (sep, matching, score)
+            })
+            .collect();
+        ranked.sort_by(|lhs, rhs| lhs.2.partial_cmp(&rhs.2).unwrap_or(std::cmp::Ordering::Equal));
+
+        let (separator, winner) = match ranked.last() {
+            Some(&(sep, matching, _)) => (sep, matching),
+            None => return Err(Error::NoDelimiterFound),
+        };
+
+        let runner_up = ranked
+            .iter()
+            .rev()
+            .nth(1)
+            .map(|(_, matching, _)| *matching)
+            .unwrap_or(0);
+
+        let confidence = if total == 0 {
+            0.0
+        } else {
+            (winner.saturating_sub(runner_up) as f64 / total as f64).clamp(0.0, 1.0)
+        };
+
+        // Seed every candidate at 0 so a separator that was seen but never dominated is reported
+        // as `0` rather than being absent from the tallies.
+        let mut tallies = separators.map(|sep| (*sep, 0)).collect::<BTreeMap<char, usize>>();
+        for (sep, (_mode, matching)) in mode_matches.into_iter() {
+            tallies.insert(sep, matching);
+        }
+
+        Ok(Dialect {
+            separator,
+            confidence,
+            tallies,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{GuessSeparator, MostFrequentLineByLine};
+    use crate::{GuessSeparator, MostConsistentFieldCount, MostFrequentLineByLine};
 
     // FIXME: test properly <3
 
@@ -147,4 +784,175 @@ mod tests {
 
         assert_eq!(guess, expected);
     }
+
+    #[test]
+    fn guess_dialect_reports_confident_winner() {
+        let text = "a;b;c\na;b;c\na;b;c";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+        let dialect = guesser.guess_dialect([';', ','].iter()).unwrap();
+
+        assert_eq!(dialect.separator, ';');
+        assert_eq!(dialect.confidence, 1.0);
+        assert_eq!(dialect.tallies.get(&';'), Some(&3));
+    }
+
+    #[test]
+    fn guess_dialect_reports_low_confidence_when_undecided() {
+        // One line votes ';', one votes ',' -> winner margin is zero.
+        let text = "a;b\na,b";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+        let dialect = guesser.guess_dialect([';', ','].iter()).unwrap();
+
+        assert_eq!(dialect.confidence, 0.0);
+        assert_eq!(dialect.tallies.get(&','), Some(&1));
+        assert_eq!(dialect.tallies.get(&';'), Some(&1));
+    }
+
+    #[test]
+    fn preamble_rows_are_skipped_before_guessing() {
+        // Three junk lines, then a uniform ';'-delimited table.
+        let text = "Export of sales\n2021-01-01\n\na;b;c\na;b;c\na;b;c\na;b;c";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+        let (guess, skipped) = guesser.guess_without_preamble([';', ','].iter()).unwrap();
+
+        assert_eq!(guess, ';');
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn detects_header_from_type_heterogeneity() {
+        use crate::HeaderGuess;
+
+        let text = "name;age;score\nalice;30;1.5\nbob;25;2.0\ncarol;41;0.9";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+
+        assert_eq!(guesser.has_header(';'), HeaderGuess::HasHeader);
+    }
+
+    #[test]
+    fn all_string_table_is_undetermined() {
+        use crate::HeaderGuess;
+
+        let text = "a;b;c\nd;e;f\ng;h;i";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+
+        assert_eq!(guesser.has_header(';'), HeaderGuess::Undetermined);
+    }
+
+    #[test]
+    fn column_types_are_inferred_from_data_rows() {
+        use crate::CellType;
+
+        let text = "name;age;score\nalice;30;1.5\nbob;25;2.0";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap();
+
+        assert_eq!(
+            guesser.column_types(';'),
+            vec![CellType::Text, CellType::Integer, CellType::Float]
+        );
+    }
+
+    #[test]
+    fn sampled_reading_stops_at_line_boundary() {
+        // A long ';' table; sampling a small byte budget should still guess ';' and never
+        // retain a partial trailing line.
+        let text = "a;b;c\n".repeat(1000);
+
+        let mut source = text.as_bytes();
+        let guesser =
+            MostFrequentLineByLine::try_new_sampled_with(&mut source, 64, 512).unwrap();
+
+        assert_eq!(guesser.guess([';', ','].iter()).unwrap(), ';');
+        assert!(guesser.content.lines().all(|line| line == "a;b;c"));
+    }
+
+    #[test]
+    fn strips_utf8_bom_before_guessing() {
+        let mut source: Vec<u8> = vec![0xEF, 0xBB, 0xBF];
+        source.extend_from_slice("a;b;c\na;b;c".as_bytes());
+
+        let mut reader = source.as_slice();
+        let guesser = MostFrequentLineByLine::try_new(&mut reader).unwrap();
+
+        assert_eq!(guesser.guess([';', ','].iter()).unwrap(), ';');
+    }
+
+    #[test]
+    fn transcodes_utf16_le_bom() {
+        let mut source: Vec<u8> = vec![0xFF, 0xFE];
+        for unit in "a;b;c".encode_utf16() {
+            source.extend_from_slice(&unit.to_le_bytes());
+        }
+
+        let mut reader = source.as_slice();
+        let guesser = MostFrequentLineByLine::try_new(&mut reader).unwrap();
+
+        assert_eq!(guesser.guess([';', ','].iter()).unwrap(), ';');
+    }
+
+    #[test]
+    fn rejects_invalid_utf8_as_unsupported_encoding() {
+        let mut reader: &[u8] = &[0xFF, 0x28, 0x80];
+        let result = MostFrequentLineByLine::try_new(&mut reader);
+
+        assert!(matches!(result, Err(crate::Error::UnsupportedEncoding)));
+    }
+
+    #[test]
+    fn quote_aware_ignores_separators_inside_quotes() {
+        // Without quoting, the many ',' inside the quoted field would win;
+        // with quoting only the single unquoted ';' counts.
+        let text = "\"Smith, John, jr.\";42\n\"Doe, Jane, sr.\";7";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap().quoted();
+        let guess = guesser.guess([';', ','].iter()).unwrap();
+
+        assert_eq!(guess, ';');
+    }
+
+    #[test]
+    fn quote_aware_handles_doubled_quote_escape() {
+        // The `""` is a literal quote inside the field; the ',' stays quoted and is ignored.
+        let text = "\"a \"\"b,c\"\" d\";x\n\"e \"\"f,g\"\" h\";y";
+
+        let mut source = text.as_bytes();
+        let guesser = MostFrequentLineByLine::try_new(&mut source).unwrap().quoted();
+        let guess = guesser.guess([';', ','].iter()).unwrap();
+
+        assert_eq!(guess, ';');
+    }
+
+    #[parameterized(
+        text = {
+            "a;b,c,d\na;b,c,d\na;b,c,d",        // ',' splits uniformly, ';' noise
+            "a,b,c;d\na,b,c;d\na,b,c;d",        // ',' yields a wider, uniform table
+            "a;b;c\na;b;c\na;b;c\n",
+        },
+        expected = {
+            ',',
+            ',',
+            ';',
+        }
+    )]
+    fn most_consistent_prefers_uniform_shape(text: &str, expected: char) {
+        let mut source = text.as_bytes();
+
+        let guesser = MostConsistentFieldCount::try_new(&mut source).unwrap();
+        let guess = guesser.guess([';', ','].iter()).unwrap();
+
+        assert_eq!(guess, expected);
+    }
 }